@@ -0,0 +1,16 @@
+use futures_util::StreamExt;
+
+fn main() -> std::io::Result<()> {
+    smol::block_on(async {
+        let mut ac_plug_events = acpid_plug::connect().await?;
+
+        while let Some(event) = ac_plug_events.next().await {
+            match event {
+                Ok(event) => println!("{:?}", event),
+                Err(why) => eprintln!("error: {}", why),
+            }
+        }
+
+        Ok(())
+    })
+}
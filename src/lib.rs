@@ -18,17 +18,334 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! The async runtime is selected via Cargo features: `rt-tokio` (default),
+//! `rt-async-std`, or `rt-smol`. Exactly one must be enabled; the public API
+//! is identical across all three.
+
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use cfg_if::cfg_if;
+
+#[cfg(any(
+    all(feature = "rt-tokio", feature = "rt-async-std"),
+    all(feature = "rt-tokio", feature = "rt-smol"),
+    all(feature = "rt-async-std", feature = "rt-smol"),
+))]
+compile_error!(
+    "only one of the `rt-tokio`, `rt-async-std`, or `rt-smol` features may be enabled at a time"
+);
+
+cfg_if! {
+    if #[cfg(feature = "rt-tokio")] {
+        use tokio::io::{AsyncBufRead as PollBufRead, BufReader};
+        use tokio::net::UnixStream;
+
+        async fn read_to_string(path: impl AsRef<Path>) -> std::io::Result<String> {
+            tokio::fs::read_to_string(path).await
+        }
+
+        async fn connect_socket(path: impl AsRef<Path>) -> std::io::Result<UnixStream> {
+            UnixStream::connect(path).await
+        }
+
+        async fn read_dir_paths(path: impl AsRef<Path>) -> std::io::Result<Vec<PathBuf>> {
+            let mut dir = match tokio::fs::read_dir(path).await {
+                Ok(dir) => dir,
+                Err(why) if why.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(why) => return Err(why),
+            };
+
+            let mut paths = Vec::new();
+            while let Some(entry) = dir.next_entry().await? {
+                paths.push(entry.path());
+            }
+
+            Ok(paths)
+        }
+    } else if #[cfg(feature = "rt-async-std")] {
+        use async_std::io::BufRead as PollBufRead;
+        use async_std::io::BufReader;
+        use async_std::os::unix::net::UnixStream;
+        use futures_util::StreamExt;
+
+        async fn read_to_string(path: impl AsRef<Path>) -> std::io::Result<String> {
+            async_std::fs::read_to_string(async_std::path::Path::new(path.as_ref())).await
+        }
+
+        async fn connect_socket(path: impl AsRef<Path>) -> std::io::Result<UnixStream> {
+            UnixStream::connect(async_std::path::Path::new(path.as_ref())).await
+        }
+
+        async fn read_dir_paths(path: impl AsRef<Path>) -> std::io::Result<Vec<PathBuf>> {
+            let mut dir = match async_std::fs::read_dir(async_std::path::Path::new(path.as_ref())).await {
+                Ok(dir) => dir,
+                Err(why) if why.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(why) => return Err(why),
+            };
+
+            let mut paths = Vec::new();
+            while let Some(entry) = dir.next().await {
+                paths.push(entry?.path().into());
+            }
+
+            Ok(paths)
+        }
+    } else if #[cfg(feature = "rt-smol")] {
+        use futures_lite::io::{AsyncBufRead as PollBufRead, BufReader};
+        use futures_util::StreamExt;
+        use smol::net::unix::UnixStream;
+
+        async fn read_to_string(path: impl AsRef<Path>) -> std::io::Result<String> {
+            smol::fs::read_to_string(path).await
+        }
+
+        async fn connect_socket(path: impl AsRef<Path>) -> std::io::Result<UnixStream> {
+            UnixStream::connect(path).await
+        }
+
+        async fn read_dir_paths(path: impl AsRef<Path>) -> std::io::Result<Vec<PathBuf>> {
+            let mut dir = match smol::fs::read_dir(path).await {
+                Ok(dir) => dir,
+                Err(why) if why.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(why) => return Err(why),
+            };
+
+            let mut paths = Vec::new();
+            while let Some(entry) = dir.next().await {
+                paths.push(entry?.path());
+            }
+
+            Ok(paths)
+        }
+    } else {
+        compile_error!("one of the `rt-tokio`, `rt-async-std`, or `rt-smol` features must be enabled");
+    }
+}
+
+/// Drives a single `read_line` to completion across possibly many polls.
+///
+/// This mirrors the internals of tokio's `Lines`/`read_line`: the scratch
+/// `buf` and `read` cursor are stored on the stream itself rather than in a
+/// freshly boxed future, so a `Poll::Pending` from `poll_fill_buf` retains
+/// whatever bytes were already copied out instead of discarding them.
+fn poll_read_line_internal<R: PollBufRead + ?Sized>(
+    mut reader: Pin<&mut R>,
+    cx: &mut Context<'_>,
+    output: &mut String,
+    buf: &mut Vec<u8>,
+    read: &mut usize,
+) -> Poll<std::io::Result<usize>> {
+    loop {
+        let (done, used) = {
+            let available = match reader.as_mut().poll_fill_buf(cx) {
+                Poll::Ready(Ok(available)) => available,
+                Poll::Ready(Err(why)) => return Poll::Ready(Err(why)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            match available.iter().position(|&byte| byte == b'\n') {
+                Some(i) => {
+                    buf.extend_from_slice(&available[..=i]);
+                    (true, i + 1)
+                }
+                None => {
+                    buf.extend_from_slice(available);
+                    (false, available.len())
+                }
+            }
+        };
+
+        reader.as_mut().consume(used);
+        *read += used;
+
+        if done {
+            let total_read = mem::replace(read, 0);
 
-use std::path::Path;
-use std::task::Poll;
+            return Poll::Ready(match String::from_utf8(mem::take(buf)) {
+                Ok(string) => {
+                    *output = string;
+                    Ok(total_read)
+                }
+                Err(_) => Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                )),
+            });
+        }
+
+        if used == 0 {
+            // EOF before a newline was found: drop any partial, unterminated
+            // line rather than surfacing it as a completed read.
+            buf.clear();
+            *read = 0;
+
+            return Poll::Ready(Ok(0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    cfg_if! {
+        if #[cfg(feature = "rt-tokio")] {
+            use std::io::Cursor;
+        } else if #[cfg(feature = "rt-async-std")] {
+            use futures_util::io::Cursor;
+        } else if #[cfg(feature = "rt-smol")] {
+            use futures_lite::io::Cursor;
+        }
+    }
+
+    /// Drives `poll_read_line_internal` to completion against an in-memory
+    /// buffer. The buffer never returns `Pending`, so a single poll suffices.
+    fn read_line_once(bytes: &[u8]) -> (usize, String) {
+        let mut reader = BufReader::new(Cursor::new(bytes.to_vec()));
+        let mut output = String::new();
+        let mut buf = Vec::new();
+        let mut read = 0;
+
+        let waker = futures_util::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        match poll_read_line_internal(
+            Pin::new(&mut reader),
+            &mut cx,
+            &mut output,
+            &mut buf,
+            &mut read,
+        ) {
+            Poll::Ready(Ok(n)) => (n, output),
+            Poll::Ready(Err(why)) => panic!("unexpected error: {why}"),
+            Poll::Pending => panic!("unexpected Pending against an in-memory buffer"),
+        }
+    }
+
+    #[test]
+    fn reads_a_newline_terminated_line() {
+        let (read, line) = read_line_once(b"ac_adapter ACPI0003:00 00000080 00000001\n");
+        assert_eq!(read, 41);
+        assert_eq!(line, "ac_adapter ACPI0003:00 00000080 00000001\n");
+    }
+
+    #[test]
+    fn eof_on_a_trailing_partial_line_is_dropped() {
+        let (read, line) = read_line_once(b"ac_adapter ACPI0003:00 00000080");
+        assert_eq!(read, 0);
+        assert!(line.is_empty());
+    }
+
+    #[test]
+    fn clean_eof_with_no_data_is_dropped() {
+        let (read, line) = read_line_once(b"");
+        assert_eq!(read, 0);
+        assert!(line.is_empty());
+    }
+
+    #[test]
+    fn parses_ac_adapter_plugged() {
+        let event = parse_acpid_line("ac_adapter ACPI0003:00 00000080 00000001");
+        assert_eq!(event, AcpiEvent::AcAdapter(Event::Plugged));
+    }
 
-use futures_util::FutureExt;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::net::UnixStream;
+    #[test]
+    fn parses_ac_adapter_unplugged() {
+        let event = parse_acpid_line("ac_adapter ACPI0003:00 00000080 00000000");
+        assert_eq!(event, AcpiEvent::AcAdapter(Event::Unplugged));
+    }
+
+    #[test]
+    fn parses_lid_open() {
+        let event = parse_acpid_line("button/lid LID 00000080 00000001");
+        assert_eq!(event, AcpiEvent::Lid { open: true });
+    }
+
+    #[test]
+    fn parses_lid_closed() {
+        let event = parse_acpid_line("button/lid LID 00000080 00000000");
+        assert_eq!(event, AcpiEvent::Lid { open: false });
+    }
+
+    #[test]
+    fn parses_power_button() {
+        let event = parse_acpid_line("button/power PBTN 00000080 00000000");
+        assert_eq!(event, AcpiEvent::PowerButton);
+    }
+
+    #[test]
+    fn parses_battery_hex_state() {
+        let event = parse_acpid_line("battery BAT0 00000080 0000001f");
+        assert_eq!(
+            event,
+            AcpiEvent::Battery {
+                device: "BAT0".to_owned(),
+                state: 0x1f,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_thermal_hex_value() {
+        let event = parse_acpid_line("thermal_zone THM0 00000080 000000ff");
+        assert_eq!(
+            event,
+            AcpiEvent::Thermal {
+                zone: "THM0".to_owned(),
+                value: 0xff,
+            }
+        );
+    }
+
+    #[test]
+    fn falls_back_to_unknown_for_unrecognized_classes() {
+        let event = parse_acpid_line("video/brightnessup BRTUP 00000087 00000000");
+        assert_eq!(
+            event,
+            AcpiEvent::Unknown {
+                class: "video/brightnessup".to_owned(),
+                raw: "video/brightnessup BRTUP 00000087 00000000".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_battery_line_with_non_hex_data_defaults_to_zero() {
+        let event = parse_acpid_line("battery BAT0 00000080 not-hex");
+        assert_eq!(
+            event,
+            AcpiEvent::Battery {
+                device: "BAT0".to_owned(),
+                state: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn short_line_with_no_data_field_does_not_panic() {
+        let event = parse_acpid_line("ac_adapter");
+        assert_eq!(event, AcpiEvent::AcAdapter(Event::Unplugged));
+    }
+
+    #[test]
+    fn empty_line_falls_back_to_unknown() {
+        let event = parse_acpid_line("");
+        assert_eq!(
+            event,
+            AcpiEvent::Unknown {
+                class: String::new(),
+                raw: String::new(),
+            }
+        );
+    }
+}
 
 const DEFAULT_SOCKET: &str = "/var/run/acpid.socket";
-const BAT0_STATUS: &str = "/sys/class/power_supply/BAT0/status";
-const BAT1_STATUS: &str = "/sys/class/power_supply/BAT1/status";
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
 
 /// Listens for AC plug events from `/var/run/acpid.socket`.
 pub async fn connect() -> std::io::Result<AcPlugEvents> {
@@ -40,6 +357,41 @@ pub async fn with_socket<P: AsRef<Path>>(socket: P) -> std::io::Result<AcPlugEve
     AcPlugEvents::with_socket(socket).await
 }
 
+/// Finds every `/sys/class/power_supply/*` entry whose `type` is `Battery`,
+/// sorted by path. This replaces the old hard-coded `BAT0`/`BAT1` lookup so
+/// systems with differently named or additional batteries (`CMB0`,
+/// `macsmc-battery`, multi-battery laptops) are seeded correctly.
+async fn discover_batteries() -> std::io::Result<Vec<PathBuf>> {
+    let mut batteries = Vec::new();
+
+    for entry in read_dir_paths(POWER_SUPPLY_DIR).await? {
+        if let Ok(kind) = read_to_string(entry.join("type")).await {
+            if kind.trim() == "Battery" {
+                batteries.push(entry);
+            }
+        }
+    }
+
+    batteries.sort();
+
+    Ok(batteries)
+}
+
+/// The adapter is considered plugged in unless every discovered battery
+/// reports `Discharging`; a system with no batteries at all is assumed to
+/// be mains-powered.
+async fn seed_plugged_state(batteries: &[PathBuf]) -> std::io::Result<bool> {
+    for battery in batteries {
+        let status = read_to_string(battery.join("status")).await?;
+
+        if status.trim() != "Discharging" {
+            return Ok(true);
+        }
+    }
+
+    Ok(batteries.is_empty())
+}
+
 /// Whether the power adapter has been plugged or unplugged.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum Event {
@@ -47,11 +399,148 @@ pub enum Event {
     Unplugged,
 }
 
-/// A stream of power adapter plug events.
-pub struct AcPlugEvents {
+/// A single event parsed off the acpid socket.
+///
+/// acpid multiplexes every kernel ACPI event over the same socket; this
+/// enum covers the event classes this crate knows how to interpret, with
+/// [`AcpiEvent::Unknown`] as a catch-all for everything else.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AcpiEvent {
+    /// The AC adapter was plugged or unplugged.
+    AcAdapter(Event),
+    /// The lid was opened or closed.
+    Lid { open: bool },
+    /// The power button was pressed.
+    PowerButton,
+    /// A battery's reported charge/discharge state changed.
+    Battery { device: String, state: u32 },
+    /// A thermal zone crossed a trip point.
+    Thermal { zone: String, value: u32 },
+    /// An acpid event class this crate does not parse further.
+    Unknown { class: String, raw: String },
+}
+
+fn parse_acpid_line(line: &str) -> AcpiEvent {
+    let mut fields = line.split_whitespace();
+    let class = fields.next().unwrap_or_default();
+    let device = fields.next().unwrap_or_default();
+    let data = fields.last();
+
+    match class {
+        "ac_adapter" => AcpiEvent::AcAdapter(if data.is_some_and(|data| data.ends_with('1')) {
+            Event::Plugged
+        } else {
+            Event::Unplugged
+        }),
+        "button/lid" => AcpiEvent::Lid {
+            open: data.is_some_and(|data| data.ends_with('1')),
+        },
+        "button/power" => AcpiEvent::PowerButton,
+        "battery" => AcpiEvent::Battery {
+            device: device.to_owned(),
+            state: data
+                .and_then(|data| u32::from_str_radix(data, 16).ok())
+                .unwrap_or(0),
+        },
+        "thermal_zone" => AcpiEvent::Thermal {
+            zone: device.to_owned(),
+            value: data
+                .and_then(|data| u32::from_str_radix(data, 16).ok())
+                .unwrap_or(0),
+        },
+        _ => AcpiEvent::Unknown {
+            class: class.to_owned(),
+            raw: line.to_owned(),
+        },
+    }
+}
+
+/// Drives a socket one line at a time, reusing the `buf`/`read` scratch
+/// state across polls instead of boxing a fresh future per poll.
+struct LineReader {
     reader: BufReader<UnixStream>,
     line: String,
+    buf: Vec<u8>,
+    read: usize,
+}
+
+impl LineReader {
+    async fn with_socket<P: AsRef<Path>>(socket: P) -> std::io::Result<Self> {
+        let stream = connect_socket(socket).await?;
+
+        Ok(Self {
+            reader: BufReader::new(stream),
+            line: String::new(),
+            buf: Vec::new(),
+            read: 0,
+        })
+    }
+
+    /// Reads the next line, or `None` at EOF.
+    fn poll_line(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<Option<String>>> {
+        let reader = Pin::new(&mut self.reader);
+
+        match poll_read_line_internal(reader, cx, &mut self.line, &mut self.buf, &mut self.read) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(why)) => Poll::Ready(Err(why)),
+            Poll::Ready(Ok(0)) => Poll::Ready(Ok(None)),
+            Poll::Ready(Ok(_)) => Poll::Ready(Ok(Some(mem::take(&mut self.line)))),
+        }
+    }
+}
+
+/// A stream of every event acpid reports, parsed into [`AcpiEvent`].
+pub struct AcpidEvents {
+    reader: LineReader,
+    batteries: Vec<PathBuf>,
+}
+
+impl AcpidEvents {
+    /// Listens for acpid events from `/var/run/acpid.socket`.
+    pub async fn connect() -> std::io::Result<Self> {
+        Self::with_socket(DEFAULT_SOCKET).await
+    }
+
+    /// Listens for acpid events from a custom socket.
+    pub async fn with_socket<P: AsRef<Path>>(socket: P) -> std::io::Result<Self> {
+        Ok(Self {
+            reader: LineReader::with_socket(socket).await?,
+            batteries: discover_batteries().await?,
+        })
+    }
+
+    /// The `/sys/class/power_supply/*` directories that were discovered to
+    /// be batteries at connect time, so [`AcpiEvent::Battery::device`] can
+    /// be attributed to the device that reported it.
+    pub fn batteries(&self) -> &[PathBuf] {
+        &self.batteries
+    }
+}
+
+impl futures_util::Stream for AcpidEvents {
+    type Item = std::io::Result<AcpiEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        match this.reader.poll_line(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Err(why)) => Poll::Ready(Some(Err(why))),
+            Poll::Ready(Ok(None)) => Poll::Ready(None),
+            Poll::Ready(Ok(Some(line))) => Poll::Ready(Some(Ok(parse_acpid_line(line.trim())))),
+        }
+    }
+}
+
+/// A stream of power adapter plug events.
+///
+/// This is a thin filter over [`AcpidEvents`] that only surfaces
+/// [`AcpiEvent::AcAdapter`] transitions, matching the crate's original,
+/// narrower API.
+pub struct AcPlugEvents {
+    events: AcpidEvents,
     plugged: bool,
+    initial_event: Option<Event>,
 }
 
 impl AcPlugEvents {
@@ -62,61 +551,78 @@ impl AcPlugEvents {
 
     /// Listens for AC plug events from a custom socket.
     pub async fn with_socket<P: AsRef<Path>>(socket: P) -> std::io::Result<Self> {
-        let stream = UnixStream::connect(socket).await?;
+        let events = AcpidEvents::with_socket(socket).await?;
+        let plugged = seed_plugged_state(events.batteries()).await?;
 
         Ok(Self {
-            reader: BufReader::new(stream),
-            line: String::new(),
-            plugged: {
-                let status = match tokio::fs::read_to_string(BAT1_STATUS).await {
-                    Ok(string) => string,
-                    Err(_) => tokio::fs::read_to_string(BAT0_STATUS).await?,
-                };
-
-                status.trim() != "Discharging"
-            },
+            events,
+            plugged,
+            initial_event: None,
         })
     }
+
+    /// Like [`Self::connect`], but the stream immediately yields one
+    /// `Event` reflecting the AC state seeded at connect time, before any
+    /// socket line is read.
+    pub async fn connect_with_initial_event() -> std::io::Result<Self> {
+        Self::with_socket_and_initial_event(DEFAULT_SOCKET).await
+    }
+
+    /// Like [`Self::with_socket`], but the stream immediately yields one
+    /// `Event` reflecting the AC state seeded at connect time, before any
+    /// socket line is read.
+    pub async fn with_socket_and_initial_event<P: AsRef<Path>>(socket: P) -> std::io::Result<Self> {
+        let mut this = Self::with_socket(socket).await?;
+        this.initial_event = Some(if this.plugged {
+            Event::Plugged
+        } else {
+            Event::Unplugged
+        });
+
+        Ok(this)
+    }
+
+    /// The AC adapter state as of the most recent event, without polling
+    /// the stream.
+    pub fn is_plugged(&self) -> bool {
+        self.plugged
+    }
+
+    /// The `/sys/class/power_supply/*` directories that were discovered to
+    /// be batteries at connect time, in the order used to seed
+    /// [`Self::is_plugged`].
+    pub fn batteries(&self) -> &[PathBuf] {
+        self.events.batteries()
+    }
 }
 
 impl futures_util::Stream for AcPlugEvents {
     type Item = std::io::Result<Event>;
 
-    fn poll_next(
-        self: std::pin::Pin<&mut Self>,
-        cx: &mut std::task::Context<'_>,
-    ) -> Poll<Option<Self::Item>> {
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let this = self.get_mut();
 
-        loop {
-            let mut line_read = Box::pin(this.reader.read_line(&mut this.line));
+        if let Some(event) = this.initial_event.take() {
+            return Poll::Ready(Some(Ok(event)));
+        }
 
-            match line_read.poll_unpin(cx) {
+        loop {
+            match Pin::new(&mut this.events).poll_next(cx) {
                 Poll::Pending => return Poll::Pending,
-                Poll::Ready(Ok(read)) => {
-                    if read == 0 {
-                        return Poll::Ready(None);
-                    }
-
-                    let read_line = &this.line[..read].trim();
-
-                    if read_line.starts_with("ac_adapter") {
-                        if this.plugged {
-                            if read_line.ends_with('0') {
-                                this.plugged = false;
-                                this.line.clear();
-                                return Poll::Ready(Some(Ok(Event::Unplugged)));
-                            }
-                        } else if read_line.ends_with('1') {
-                            this.plugged = true;
-                            this.line.clear();
-                            return Poll::Ready(Some(Ok(Event::Plugged)));
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Ready(Some(Err(why))) => return Poll::Ready(Some(Err(why))),
+                Poll::Ready(Some(Ok(AcpiEvent::AcAdapter(event)))) => {
+                    if this.plugged {
+                        if event == Event::Unplugged {
+                            this.plugged = false;
+                            return Poll::Ready(Some(Ok(Event::Unplugged)));
                         }
+                    } else if event == Event::Plugged {
+                        this.plugged = true;
+                        return Poll::Ready(Some(Ok(Event::Plugged)));
                     }
-
-                    this.line.clear();
                 }
-                Poll::Ready(Err(why)) => return Poll::Ready(Some(Err(why))),
+                Poll::Ready(Some(Ok(_))) => {}
             }
         }
     }